@@ -2,19 +2,38 @@ use deku::{
     ctx::{Endian, Limit},
     prelude::*,
 };
-use std::{collections::BTreeMap, mem::size_of};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek, SeekFrom, Write},
+    mem::size_of,
+};
 
+/// `jobs` is the entry-codec worker-count knob (`1` = serial), threaded through to
+/// [`Entries::read`]/[`Entries::write`] the same way `index` already is, rather than a global:
+/// callers that want parallel encode/decode pass it explicitly at the call site.
+///
+/// `verify_checksums` likewise controls whether [`Entries::read`] verifies the digests
+/// recorded in a `Checksums` (0xF1) special entry, if present; it's a read-only parse option
+/// (ignored on write) rather than a global, so trusted callers can skip the verification pass
+/// without affecting every other reader in the process.
 #[derive(Clone, Debug, Default, DekuRead, DekuWrite)]
-#[deku(magic = b"\0T\0M\0v\x01", endian = "little")]
+#[deku(
+    magic = b"\0T\0M\0v\x01",
+    endian = "little",
+    ctx = "jobs: usize, verify_checksums: bool"
+)]
 struct Container {
     mode: Mode,
     #[deku(update = "{ use crate::parsers::INDIC_SIZE; self.index.len() as u64 * INDIC_SIZE }")]
     index_bytes: u64,
-    #[deku(update = "self.entries.len()")]
+    #[deku(update = "{ use crate::parsers::entries_byte_len; entries_byte_len(&self.entries) }")]
     entries_bytes: u64,
     #[deku(count = "index_bytes / INDIC_SIZE")]
     index: Vec<Indic>,
-    #[deku(bytes_read = "entries_bytes", ctx = "index")]
+    #[deku(
+        bytes_read = "entries_bytes",
+        ctx = "index, ParallelConfig { jobs, fanout: 4 }, verify_checksums"
+    )]
     entries: Entries,
 }
 
@@ -71,11 +90,20 @@ impl Default for Mode {
 
 #[derive(Clone, Copy, Debug, DekuRead, DekuWrite, Eq, PartialEq, Ord, PartialOrd)]
 #[deku(type = "u8", ctx = "_: Endian")]
+#[repr(u8)]
 enum IndicKind {
     #[deku(id = "0x01")]
     File,
     #[deku(id = "0x02")]
     Dir,
+    #[deku(id = "0x03")]
+    Symlink,
+    #[deku(id = "0x04")]
+    CharDevice,
+    #[deku(id = "0x05")]
+    BlockDevice,
+    #[deku(id = "0x06")]
+    Fifo,
 
     #[deku(id = "0x10")]
     Attributes,
@@ -107,10 +135,6 @@ struct Path {
     segments: Vec<PathSeg>,
 }
 
-// todo for paths and attrs entries: add a lookup table/tree for the offset of the paths/attrs in
-// the entry given an path's index, so the entry can be partially decoded instead of loading it all
-// in memory at once or parsing N - 1 paths to find the Nth path.
-
 #[derive(Clone, Copy, Debug, DekuRead, DekuWrite, Eq, PartialEq, Ord, PartialOrd)]
 #[deku(ctx = "_: Endian")]
 struct Lookup {
@@ -119,6 +143,57 @@ struct Lookup {
 }
 const LOOKUP_SIZE: usize = size_of::<u32>() + size_of::<u64>();
 
+/// Parses a `(count: u32, lookup: [Lookup; count], ...)` prefix shared by `PathsEntry` and
+/// `AttributesEntry`, returning the table and the byte offset (from the start of `data`)
+/// where the records themselves begin.
+fn parse_lookup_prefix(data: &[u8]) -> Result<(Vec<Lookup>, usize), DekuError> {
+    if data.len() < size_of::<u32>() {
+        return Err(DekuError::Parse("entry truncated before count".into()));
+    }
+    let count = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
+    let lookup_start = size_of::<u32>();
+    let lookup_end = lookup_start + count * LOOKUP_SIZE;
+    if data.len() < lookup_end {
+        return Err(DekuError::Parse(
+            "entry truncated before lookup table".into(),
+        ));
+    }
+
+    let mut lookup = Vec::with_capacity(count);
+    for chunk in data[lookup_start..lookup_end].chunks_exact(LOOKUP_SIZE) {
+        let index = u32::from_le_bytes(chunk[0..4].try_into().unwrap());
+        let offset = u64::from_le_bytes(chunk[4..12].try_into().unwrap());
+        lookup.push(Lookup { index, offset });
+    }
+
+    Ok((lookup, lookup_end))
+}
+
+/// Binary-searches a (sorted, 1-indexed) lookup table for `index` and parses a single `T`
+/// record at its recorded offset, instead of decoding every record before it.
+fn lookup_get<T>(lookup: &[Lookup], data: &[u8], index: u32) -> Result<Option<T>, DekuError>
+where
+    T: for<'a> DekuRead<'a, Endian>,
+{
+    let Ok(pos) = lookup.binary_search_by_key(&index, |entry| entry.index) else {
+        return Ok(None);
+    };
+    // `Lookup::offset` is recorded as a bit offset from the start of the entry (it's taken
+    // straight from the in-progress `BitVec`'s length while writing); the format is always
+    // byte-aligned in practice, so this divides evenly.
+    let offset = (lookup[pos].offset / 8) as usize;
+    if offset > data.len() {
+        return Err(DekuError::Parse(format!(
+            "lookup offset {offset} is past the end of the entry"
+        )));
+    }
+
+    let bits = BitVec::<Msb0, u8>::try_from_vec(data[offset..].to_vec())
+        .map_err(|_| DekuError::Parse("entry data not byte-aligned".into()))?;
+    let (_, item) = T::read(&bits, Endian::Little)?;
+    Ok(Some(item))
+}
+
 fn write_lookup<T: DekuWrite<Endian>>(
     list: &Vec<T>,
     output: &mut BitVec<Msb0, u8>,
@@ -150,50 +225,305 @@ fn write_lookup<T: DekuWrite<Endian>>(
     Ok(())
 }
 
-#[derive(Clone, Debug, DekuRead, Eq, PartialEq, Ord, PartialOrd)]
-#[deku(endian = "little")]
+/// Lazy view over a decoded Paths (0xF0) special entry: the lookup table is retained, but
+/// individual paths are only decoded on demand via [`PathsEntry::get`] instead of all of them
+/// being parsed up front.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 struct PathsEntry {
-    #[deku(bytes = 4)]
-    path_count: usize,
-    #[deku(
-        count = "*path_count * LOOKUP_SIZE",
-        map = "|_: Vec<u8>| -> Result<(), DekuError> { Ok(()) }"
-    )]
-    _lookup: (), // parsed but discarded (only useful when doing partial parses)
-    #[deku(count = "path_count")]
-    paths: Vec<Path>,
+    lookup: Vec<Lookup>,
+    /// Decoded entry bytes, indexed into via `lookup[i].offset`
+    data: Vec<u8>,
+}
+
+impl PathsEntry {
+    fn parse(data: &[u8]) -> Result<Self, DekuError> {
+        let (lookup, _) = parse_lookup_prefix(data)?;
+        Ok(Self {
+            lookup,
+            data: data.to_vec(),
+        })
+    }
+
+    /// Builds a Paths entry's on-wire bytes (count, lookup table, then records) from `paths`
+    /// in order, 1-indexing them the same way [`Indic::path`] does. This is the write-side
+    /// counterpart of [`PathsEntry::parse`]/[`PathsEntry::get`].
+    fn from_paths(paths: &[Path]) -> Result<Self, DekuError> {
+        let mut output = BitVec::new();
+        write_lookup(&paths.to_vec(), &mut output, Endian::Little)?;
+        Self::parse(&output.into_vec())
+    }
+
+    fn len(&self) -> usize {
+        self.lookup.len()
+    }
+
+    /// Decodes only the path at `index` (1-indexed, matching an [`Indic::path`] value) by
+    /// seeking to its recorded offset instead of parsing every preceding path.
+    fn get(&self, index: u32) -> Result<Option<Path>, DekuError> {
+        lookup_get(&self.lookup, &self.data, index)
+    }
 }
 
 impl DekuWrite<Endian> for PathsEntry {
-    fn write(&self, output: &mut BitVec<Msb0, u8>, ctx: Endian) -> Result<(), DekuError> {
-        write_lookup(&self.paths, output, ctx)
+    fn write(&self, output: &mut BitVec<Msb0, u8>, _: Endian) -> Result<(), DekuError> {
+        let mut bits = BitVec::<Msb0, u8>::try_from_vec(self.data.clone())
+            .map_err(|_| DekuError::Parse("entry data not byte-aligned".into()))?;
+        output.append(&mut bits);
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, DekuRead, DekuWrite, Eq, PartialEq, Ord, PartialOrd)]
 #[deku(ctx = "_: Endian")]
-struct Attributes {
+struct AttributesV1 {
+    mode: u16,
+}
+
+/// Rich, forward-compatible attributes record: mode plus ownership, timestamps, an optional
+/// symlink/hardlink target, and arbitrary extended attributes.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+struct AttributesV2 {
     mode: u16,
+    uid: u32,
+    gid: u32,
+    mtime: i64,
+    /// Symlink/hardlink target, for the new `Symlink` indic kind
+    target: Option<Path>,
+    /// Arbitrary extended attributes, keyed and valued by raw bytes like path segments
+    xattrs: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl DekuRead<'_, Endian> for AttributesV2 {
+    fn read<'bs>(
+        input: &'bs BitSlice<Msb0, u8>,
+        endian: Endian,
+    ) -> Result<(&'bs BitSlice<Msb0, u8>, Self), DekuError> {
+        let (rest, mode) = u16::read(input, endian)?;
+        let (rest, uid) = u32::read(rest, endian)?;
+        let (rest, gid) = u32::read(rest, endian)?;
+        let (rest, mtime) = i64::read(rest, endian)?;
+        let (rest, has_target) = u8::read(rest, endian)?;
+        let (rest, target) = if has_target == 1 {
+            let (rest, target) = Path::read(rest, endian)?;
+            (rest, Some(target))
+        } else {
+            (rest, None)
+        };
+
+        let (mut rest, xattr_count) = u32::read(rest, endian)?;
+        let mut xattrs = BTreeMap::new();
+        for _ in 0..xattr_count {
+            let (r, key_len) = u32::read(rest, endian)?;
+            let (r, key) = Vec::<u8>::read(r, ((key_len as usize).into(), ()))?;
+            let (r, value_len) = u32::read(r, endian)?;
+            let (r, value) = Vec::<u8>::read(r, ((value_len as usize).into(), ()))?;
+            xattrs.insert(key, value);
+            rest = r;
+        }
+
+        Ok((
+            rest,
+            Self {
+                mode,
+                uid,
+                gid,
+                mtime,
+                target,
+                xattrs,
+            },
+        ))
+    }
+}
+
+impl DekuWrite<Endian> for AttributesV2 {
+    fn write(&self, output: &mut BitVec<Msb0, u8>, endian: Endian) -> Result<(), DekuError> {
+        self.mode.write(output, endian)?;
+        self.uid.write(output, endian)?;
+        self.gid.write(output, endian)?;
+        self.mtime.write(output, endian)?;
+        (self.target.is_some() as u8).write(output, endian)?;
+        if let Some(target) = &self.target {
+            target.write(output, endian)?;
+        }
+        (self.xattrs.len() as u32).write(output, endian)?;
+        for (key, value) in &self.xattrs {
+            (key.len() as u32).write(output, endian)?;
+            key.write(output, ())?;
+            (value.len() as u32).write(output, endian)?;
+            value.write(output, ())?;
+        }
+        Ok(())
+    }
+}
+
+/// Tagged enum of attribute-record versions, so older archives written before richer
+/// attributes existed still parse: the reader dispatches on the leading version byte.
+#[derive(Clone, Debug, DekuRead, DekuWrite, Eq, PartialEq, Ord, PartialOrd)]
+#[deku(type = "u8", ctx = "_: Endian")]
+enum Attributes {
+    #[deku(id = "0x01")]
+    V1(AttributesV1),
+    #[deku(id = "0x02")]
+    V2(AttributesV2),
+}
+
+/// Lazy view over a decoded Attributes (0x10) special entry, mirroring [`PathsEntry`].
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct AttributesEntry {
+    lookup: Vec<Lookup>,
+    /// Decoded entry bytes, indexed into via `lookup[i].offset`
+    data: Vec<u8>,
+}
+
+impl AttributesEntry {
+    fn parse(data: &[u8]) -> Result<Self, DekuError> {
+        let (lookup, _) = parse_lookup_prefix(data)?;
+        Ok(Self {
+            lookup,
+            data: data.to_vec(),
+        })
+    }
+
+    /// Builds an Attributes entry's on-wire bytes (count, lookup table, then records) from
+    /// `records` in order, 1-indexing them the same way [`Indic::attrs`] does. This is the
+    /// write-side counterpart of [`AttributesEntry::parse`]/[`AttributesEntry::get`].
+    fn from_records(records: &[Attributes]) -> Result<Self, DekuError> {
+        let mut output = BitVec::new();
+        write_lookup(&records.to_vec(), &mut output, Endian::Little)?;
+        Self::parse(&output.into_vec())
+    }
+
+    fn len(&self) -> usize {
+        self.lookup.len()
+    }
+
+    /// Decodes only the attributes record at `index` (1-indexed, matching an
+    /// [`Indic::attrs`] value) by seeking to its recorded offset instead of parsing every
+    /// preceding record.
+    fn get(&self, index: u32) -> Result<Option<Attributes>, DekuError> {
+        lookup_get(&self.lookup, &self.data, index)
+    }
+}
+
+impl DekuWrite<Endian> for AttributesEntry {
+    fn write(&self, output: &mut BitVec<Msb0, u8>, _: Endian) -> Result<(), DekuError> {
+        let mut bits = BitVec::<Msb0, u8>::try_from_vec(self.data.clone())
+            .map_err(|_| DekuError::Parse("entry data not byte-aligned".into()))?;
+        output.append(&mut bits);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, DekuRead, DekuWrite, Eq, PartialEq, Ord, PartialOrd)]
+#[deku(type = "u8", ctx = "_: Endian")]
+enum ChecksumAlgorithm {
+    #[deku(id = "0x01")]
+    Crc32,
+    #[deku(id = "0x02")]
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    fn digest_len(self) -> usize {
+        match self {
+            Self::Crc32 => 4,
+            Self::Sha256 => 32,
+        }
+    }
+}
+
+#[derive(Clone, Debug, DekuRead, DekuWrite, Eq, PartialEq, Ord, PartialOrd)]
+#[deku(ctx = "_: Endian")]
+struct ChecksumRecord {
+    /// Index (position in the index) of the indic this digest covers
+    target: u32,
+    algorithm: ChecksumAlgorithm,
+    #[deku(count = "algorithm.digest_len()")]
+    digest: Vec<u8>,
 }
 
 #[derive(Clone, Debug, DekuRead, Eq, PartialEq, Ord, PartialOrd)]
 #[deku(endian = "little")]
-struct AttributesEntry {
+struct ChecksumsEntry {
     #[deku(bytes = 4)]
-    attr_count: usize,
+    record_count: usize,
     #[deku(
-        count = "*attr_count * LOOKUP_SIZE",
+        count = "*record_count * LOOKUP_SIZE",
         map = "|_: Vec<u8>| -> Result<(), DekuError> { Ok(()) }"
     )]
     _lookup: (), // parsed but discarded (only useful when doing partial parses)
-    #[deku(count = "attr_count")]
-    attrs: Vec<Attributes>,
+    #[deku(count = "record_count")]
+    records: Vec<ChecksumRecord>,
 }
 
-impl DekuWrite<Endian> for AttributesEntry {
+impl DekuWrite<Endian> for ChecksumsEntry {
     fn write(&self, output: &mut BitVec<Msb0, u8>, ctx: Endian) -> Result<(), DekuError> {
-        write_lookup(&self.attrs, output, ctx)
+        write_lookup(&self.records, output, ctx)
+    }
+}
+
+/// XOR seed mixed into checksums covering special (0xF0 and above) entries, so a digest
+/// recorded against a special entry can't accidentally validate against file data, or vice versa.
+const CSUM_XOR_SPECIAL: u32 = u32::from_le_bytes(*b"SPEC");
+/// XOR seed mixed into checksums covering ordinary file/dir data entries.
+const CSUM_XOR_FILEDATA: u32 = u32::from_le_bytes(*b"FDAT");
+
+/// The on-disk `#[deku(id = ...)]` byte for `kind`. `kind as u8` would give the Rust enum's
+/// positional discriminant instead (0, 1, 2, ...), not the wire id, since `#[deku(id = ...)]`
+/// doesn't set the Rust discriminant.
+fn indic_kind_id(kind: IndicKind) -> u8 {
+    let mut bits = BitVec::new();
+    kind.write(&mut bits, Endian::Little)
+        .expect("IndicKind always serializes to a single byte");
+    bits.into_vec()[0]
+}
+
+fn region_csum_seed(kind: IndicKind) -> u32 {
+    if indic_kind_id(kind) >= 0xF0 {
+        CSUM_XOR_SPECIAL
+    } else {
+        CSUM_XOR_FILEDATA
+    }
+}
+
+fn checksum_digest(algorithm: ChecksumAlgorithm, seed_xor: u32, data: &[u8]) -> Vec<u8> {
+    let mut digest = match algorithm {
+        ChecksumAlgorithm::Crc32 => {
+            let mut hasher = crc32fast::Hasher::new();
+            hasher.update(data);
+            hasher.finalize().to_le_bytes().to_vec()
+        }
+        ChecksumAlgorithm::Sha256 => {
+            use sha2::Digest;
+            let mut hasher = sha2::Sha256::new();
+            hasher.update(data);
+            hasher.finalize().to_vec()
+        }
+    };
+    for (byte, seed_byte) in digest.iter_mut().zip(seed_xor.to_le_bytes().iter().cycle()) {
+        *byte ^= seed_byte;
     }
+    digest
+}
+
+/// Recomputes and compares the digest for every record in `checksums` against the already-
+/// decoded entries it covers, returning an error on the first mismatch.
+fn verify_checksums(entries: &[Entry], checksums: &ChecksumsEntry) -> Result<(), DekuError> {
+    for record in &checksums.records {
+        let target = record.target as usize;
+        let target_entry = entries.get(target).ok_or_else(|| {
+            DekuError::Parse(format!("checksum record targets unknown entry {target}"))
+        })?;
+
+        let seed = region_csum_seed(target_entry.indic.kind);
+        let expected = checksum_digest(record.algorithm, seed, &target_entry.data);
+        if expected != record.digest {
+            return Err(DekuError::Parse(format!(
+                "checksum mismatch for entry {target}: archive is corrupt or was tampered with"
+            )));
+        }
+    }
+    Ok(())
 }
 
 #[derive(Clone, Copy, Debug, DekuRead, DekuWrite)]
@@ -226,6 +556,8 @@ enum Encoding {
     Raw,
     #[deku(id = "0x01")]
     Zstd,
+    #[deku(id = "0x02")]
+    Lz4,
 
     #[deku(id = "0xFE")]
     Custom,
@@ -246,6 +578,26 @@ struct ZstdParams {
     dictionary: u64,
 }
 
+#[derive(Clone, Copy, Debug, DekuRead, DekuWrite, Eq, PartialEq, Ord, PartialOrd)]
+#[deku(type = "u8", ctx = "_: Endian")]
+enum Lz4Format {
+    /// Raw LZ4 block, with the decompressed size recorded as a little-endian u32 prefix
+    /// since raw blocks carry no framing of their own.
+    #[deku(id = "0x00")]
+    Block,
+    /// Self-describing LZ4 frame format.
+    #[deku(id = "0x01")]
+    Frame,
+}
+
+#[derive(Clone, Copy, Debug, DekuRead, DekuWrite, Eq, PartialEq, Ord, PartialOrd)]
+#[deku(endian = "little")]
+struct Lz4Params {
+    format: Lz4Format,
+    /// Index of the indic that points to the LZ4 preset-dictionary data file, or 0 if none
+    dictionary: u64,
+}
+
 #[derive(Clone, Debug, DekuRead, DekuWrite, Eq, PartialEq, Ord, PartialOrd)]
 #[deku(type = "u8", endian = "little")]
 enum CustomParams {
@@ -260,13 +612,192 @@ struct EntryHeader {
     has_params: u8,
     #[deku(bits = 1)]
     nested: u8,
-    #[deku(bits = 6)]
+    /// Data is a sparse segment stream (see [`sparsify`]/[`materialize_sparse`]) rather than
+    /// the logical bytes directly.
+    #[deku(bits = 1)]
+    sparse: u8,
+    #[deku(bits = 5)]
     _reserved: u8,
     encoding: Encoding,
     #[deku(update = "self.params.len()", cond = "*has_params == 1", default = "0")]
     params_bytes: u16,
     #[deku(count = "params_bytes")]
     params: Vec<u8>,
+    #[deku(cond = "*sparse == 1")]
+    sparse_params: Option<SparseParams>,
+}
+
+#[derive(Clone, Copy, Debug, DekuRead, DekuWrite, Eq, PartialEq, Ord, PartialOrd)]
+#[deku(endian = "little")]
+struct SparseParams {
+    /// Zero runs shorter than this are kept inline instead of being turned into a hole
+    min_hole_size: u64,
+    /// The full logical length of the file, since a trailing hole (e.g. a disk image that
+    /// ends in zeros) would otherwise leave nothing in the segment stream to recover it from
+    logical_length: u64,
+}
+
+/// Reconstructs the full logical byte stream from a sparse segment stream: a sequence of
+/// `(logical_offset: u64, len: u64, bytes)` records with implicit zero-filled holes between
+/// them, consumed until the buffer is exhausted, then padded with zeros out to
+/// `logical_length` to recover a trailing hole.
+fn materialize_sparse(segments: &[u8], logical_length: u64) -> Result<Vec<u8>, DekuError> {
+    const RECORD_HEADER_LEN: usize = size_of::<u64>() * 2;
+
+    let mut logical = Vec::new();
+    let mut cursor = segments;
+    while !cursor.is_empty() {
+        if cursor.len() < RECORD_HEADER_LEN {
+            return Err(DekuError::Parse(
+                "sparse segment stream truncated before offset/len".into(),
+            ));
+        }
+        let offset = u64::from_le_bytes(cursor[0..8].try_into().unwrap()) as usize;
+        let len = u64::from_le_bytes(cursor[8..16].try_into().unwrap()) as usize;
+        cursor = &cursor[RECORD_HEADER_LEN..];
+
+        if cursor.len() < len {
+            return Err(DekuError::Parse(
+                "sparse segment data shorter than its declared length".into(),
+            ));
+        }
+        if logical.len() < offset {
+            logical.resize(offset, 0);
+        }
+        logical.extend_from_slice(&cursor[..len]);
+        cursor = &cursor[len..];
+    }
+
+    let logical_length = logical_length as usize;
+    if logical.len() < logical_length {
+        logical.resize(logical_length, 0);
+    }
+    Ok(logical)
+}
+
+/// Scans `data` for zero runs at least `min_hole_size` long and emits only the non-hole
+/// segments as `(logical_offset: u64, len: u64, bytes)` records, so the holes aren't stored
+/// or compressed at all.
+fn sparsify(data: &[u8], min_hole_size: u64) -> Vec<u8> {
+    let min_hole_size = (min_hole_size as usize).max(1);
+
+    let mut holes = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        if data[i] == 0 {
+            let start = i;
+            while i < data.len() && data[i] == 0 {
+                i += 1;
+            }
+            if i - start >= min_hole_size {
+                holes.push((start, i));
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut emit_segment = |out: &mut Vec<u8>, start: usize, end: usize| {
+        if start == end {
+            return;
+        }
+        out.extend_from_slice(&(start as u64).to_le_bytes());
+        out.extend_from_slice(&((end - start) as u64).to_le_bytes());
+        out.extend_from_slice(&data[start..end]);
+    };
+
+    let mut pos = 0;
+    for (hole_start, hole_end) in holes {
+        emit_segment(&mut out, pos, hole_start);
+        pos = hole_end;
+    }
+    emit_segment(&mut out, pos, data.len());
+
+    out
+}
+
+/// Decodes an entry's on-disk bytes according to its header's encoding, producing the
+/// logical bytes the entry represents.
+fn decode_entry_data(header: &EntryHeader, raw: &[u8]) -> Result<Vec<u8>, DekuError> {
+    match header.encoding {
+        Encoding::Raw => Ok(raw.to_vec()),
+        Encoding::Zstd => zstd::stream::decode_all(raw)
+            .map_err(|e| DekuError::Parse(format!("zstd decode failed: {e}"))),
+        Encoding::Lz4 => {
+            let (_, params) = Lz4Params::from_bytes((&header.params, 0))?;
+            lz4_decompress(raw, &params)
+        }
+        // nested/custom encodings aren't decoded at this layer
+        Encoding::Custom | Encoding::Tomo => Ok(raw.to_vec()),
+    }
+}
+
+/// Encodes an entry's logical bytes into on-disk bytes according to its header's encoding.
+fn encode_entry_data(header: &EntryHeader, data: &[u8]) -> Result<Vec<u8>, DekuError> {
+    match header.encoding {
+        Encoding::Raw => Ok(data.to_vec()),
+        Encoding::Zstd => zstd::stream::encode_all(data, 0)
+            .map_err(|e| DekuError::Parse(format!("zstd encode failed: {e}"))),
+        Encoding::Lz4 => {
+            let (_, params) = Lz4Params::from_bytes((&header.params, 0))?;
+            lz4_compress(data, &params)
+        }
+        Encoding::Custom | Encoding::Tomo => Ok(data.to_vec()),
+    }
+}
+
+fn lz4_compress(data: &[u8], params: &Lz4Params) -> Result<Vec<u8>, DekuError> {
+    match params.format {
+        Lz4Format::Block => {
+            // length-prefixed: raw blocks carry no decompressed-size framing of their own, so
+            // the size has to travel alongside the block, like a streaming log engine's
+            // per-record LZ4 blocks.
+            let compressed = lz4::block::compress(data, None, false)
+                .map_err(|e| DekuError::Parse(format!("lz4 block compress failed: {e}")))?;
+            let mut out = Vec::with_capacity(size_of::<u32>() + compressed.len());
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            out.extend_from_slice(&compressed);
+            Ok(out)
+        }
+        Lz4Format::Frame => {
+            let mut out = Vec::new();
+            let mut encoder = lz4::EncoderBuilder::new()
+                .build(&mut out)
+                .map_err(|e| DekuError::Parse(format!("lz4 frame encoder failed: {e}")))?;
+            encoder
+                .write_all(data)
+                .map_err(|e| DekuError::Parse(format!("lz4 frame write failed: {e}")))?;
+            let (_, result) = encoder.finish();
+            result.map_err(|e| DekuError::Parse(format!("lz4 frame finish failed: {e}")))?;
+            Ok(out)
+        }
+    }
+}
+
+fn lz4_decompress(raw: &[u8], params: &Lz4Params) -> Result<Vec<u8>, DekuError> {
+    match params.format {
+        Lz4Format::Block => {
+            if raw.len() < size_of::<u32>() {
+                return Err(DekuError::Parse(
+                    "lz4 block entry truncated before decompressed-size prefix".into(),
+                ));
+            }
+            let (size_bytes, compressed) = raw.split_at(size_of::<u32>());
+            let original_size = u32::from_le_bytes(size_bytes.try_into().unwrap()) as i32;
+            lz4::block::decompress(compressed, Some(original_size))
+                .map_err(|e| DekuError::Parse(format!("lz4 block decompress failed: {e}")))
+        }
+        Lz4Format::Frame => {
+            let mut decoder = lz4::Decoder::new(raw)
+                .map_err(|e| DekuError::Parse(format!("lz4 frame decoder failed: {e}")))?;
+            let mut out = Vec::new();
+            decoder
+                .read_to_end(&mut out)
+                .map_err(|e| DekuError::Parse(format!("lz4 frame read failed: {e}")))?;
+            Ok(out)
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -278,12 +809,212 @@ struct Entry {
 
 impl<T: Copy> DekuWrite<T> for Entry {
     fn write(&self, output: &mut BitVec<Msb0, u8>, _: T) -> Result<(), DekuError> {
+        let logical = if self.header.sparse == 1 {
+            let min_hole_size = self
+                .header
+                .sparse_params
+                .as_ref()
+                .map(|params| params.min_hole_size)
+                .unwrap_or(1);
+            sparsify(&self.data, min_hole_size)
+        } else {
+            self.data.clone()
+        };
+        let encoded = encode_entry_data(&self.header, &logical)?;
         self.header.write(output, ())?;
-        self.data.write(output, ())?;
+        encoded.write(output, ())?;
         Ok(())
     }
 }
 
+/// Decodes a single entry from its own bit-span (i.e. `bits` must already be sliced to
+/// exactly `[indic.offset, indic.offset + indic.length)`), shared by the serial and parallel
+/// read paths.
+fn decode_one_entry(bits: &BitSlice<Msb0, u8>, indic: Indic) -> Result<Entry, DekuError> {
+    let length = bits.len();
+    let (post_header, header) = EntryHeader::read(bits, ())?;
+    let header_length = length - post_header.len();
+    let data_length = length - header_length;
+    let data_bits = &bits[header_length..];
+    assert_eq!(
+        data_bits.len(),
+        data_length,
+        "entry data length remaining vs calculated"
+    );
+
+    let (rest, raw) = Vec::read(data_bits, ((data_length / 8).into(), ()))?;
+    assert_eq!(rest.len(), 0, "remaining data after vec read");
+    let codec_decoded = decode_entry_data(&header, &raw)?;
+    let data = if header.sparse == 1 {
+        let logical_length = header
+            .sparse_params
+            .as_ref()
+            .map(|params| params.logical_length)
+            .unwrap_or(codec_decoded.len() as u64);
+        materialize_sparse(&codec_decoded, logical_length)?
+    } else {
+        codec_decoded
+    };
+
+    Ok(Entry {
+        indic,
+        header,
+        data,
+    })
+}
+
+/// Serializes a single entry into its own standalone byte buffer, shared by the serial and
+/// parallel write paths.
+fn encode_one_entry(entry: &Entry) -> Result<Vec<u8>, DekuError> {
+    let mut output = BitVec::new();
+    entry.write(&mut output, ())?;
+    Ok(output.into_vec())
+}
+
+/// Tunable knobs for [`decode_entries_parallel`]/[`encode_entries_parallel`].
+#[derive(Clone, Copy, Debug)]
+struct ParallelConfig {
+    /// Number of worker threads to use.
+    jobs: usize,
+    /// Fan-out factor: the chunk size handed to each worker run is `entry_count / (jobs *
+    /// fanout)`, so each worker processes several independently-shuffled chunks rather than
+    /// one big contiguous span, keeping throughput even when cheap `Raw` entries and
+    /// expensive zstd-dictionary entries are unevenly distributed across the index.
+    fanout: usize,
+}
+
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        Self {
+            jobs: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            fanout: 4,
+        }
+    }
+}
+
+fn parallel_chunk_size(entry_count: usize, config: &ParallelConfig) -> usize {
+    let jobs = config.jobs.max(1);
+    let fanout = config.fanout.max(1);
+    (entry_count / (jobs * fanout)).max(1)
+}
+
+fn parallel_chunk_ranges(len: usize, size: usize) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < len {
+        let end = (start + size).min(len);
+        ranges.push((start, end));
+        start = end;
+    }
+    ranges
+}
+
+/// Distributes contiguous runs round-robin across `jobs` workers, so no single worker gets
+/// stuck with one long span of expensive entries while the others idle on cheap ones.
+fn parallel_shuffle_runs(ranges: Vec<(usize, usize)>, jobs: usize) -> Vec<Vec<(usize, usize)>> {
+    let mut workers: Vec<Vec<(usize, usize)>> = vec![Vec::new(); jobs.max(1)];
+    for (i, range) in ranges.into_iter().enumerate() {
+        workers[i % workers.len()].push(range);
+    }
+    workers
+}
+
+/// Decodes every entry named by `index` independently and in parallel, reassembling the
+/// results in index order. Each worker thread gets a shuffled set of `(start, end)` runs of
+/// index positions rather than one contiguous block, since every entry carries its own offset/length
+/// and is encoded independently, making the work embarrassingly parallel.
+fn decode_entries_parallel(
+    input: &BitSlice<Msb0, u8>,
+    index: &[Indic],
+    config: &ParallelConfig,
+) -> Result<Vec<Entry>, DekuError> {
+    let size = parallel_chunk_size(index.len(), config);
+    let ranges = parallel_chunk_ranges(index.len(), size);
+    let workers = parallel_shuffle_runs(ranges, config.jobs);
+
+    let results: std::sync::Mutex<Vec<Option<Entry>>> =
+        std::sync::Mutex::new((0..index.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| -> Result<(), DekuError> {
+        let handles: Vec<_> = workers
+            .into_iter()
+            .map(|worker_ranges| {
+                let results = &results;
+                scope.spawn(move || -> Result<(), DekuError> {
+                    for (start, end) in worker_ranges {
+                        for i in start..end {
+                            let indic = index[i];
+                            let entry_start = (indic.offset * 8) as usize;
+                            let entry_end = entry_start + (indic.length * 8) as usize;
+                            let entry = decode_one_entry(&input[entry_start..entry_end], indic)?;
+                            results.lock().unwrap()[i] = Some(entry);
+                        }
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("entry decode worker panicked")?;
+        }
+        Ok(())
+    })?;
+
+    Ok(results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.expect("every chunk range should have been decoded"))
+        .collect())
+}
+
+/// Encodes every entry independently and in parallel, reassembling the resulting byte
+/// buffers in index order. See [`decode_entries_parallel`] for the chunking strategy.
+fn encode_entries_parallel(
+    entries: &[Entry],
+    config: &ParallelConfig,
+) -> Result<Vec<u8>, DekuError> {
+    let size = parallel_chunk_size(entries.len(), config);
+    let ranges = parallel_chunk_ranges(entries.len(), size);
+    let workers = parallel_shuffle_runs(ranges, config.jobs);
+
+    let results: std::sync::Mutex<Vec<Option<Vec<u8>>>> =
+        std::sync::Mutex::new((0..entries.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| -> Result<(), DekuError> {
+        let handles: Vec<_> = workers
+            .into_iter()
+            .map(|worker_ranges| {
+                let results = &results;
+                scope.spawn(move || -> Result<(), DekuError> {
+                    for (start, end) in worker_ranges {
+                        for i in start..end {
+                            let encoded = encode_one_entry(&entries[i])?;
+                            results.lock().unwrap()[i] = Some(encoded);
+                        }
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("entry encode worker panicked")?;
+        }
+        Ok(())
+    })?;
+
+    Ok(results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flat_map(|buf| buf.expect("every chunk range should have been encoded"))
+        .collect())
+}
+
 #[derive(Clone, Debug, Default)]
 struct Entries {
     entries: Vec<Entry>,
@@ -294,72 +1025,239 @@ impl Entries {
     pub fn len(&self) -> usize {
         self.entries.len()
     }
+
+    /// The total serialized byte length of every entry, i.e. the size of the data section
+    /// `Container::entries_bytes` must record so [`Entries::read`]'s `bytes_read` window lines
+    /// up with what [`Entries::write`] actually produces.
+    fn encoded_len(&self) -> Result<u64, DekuError> {
+        let mut total = 0u64;
+        for entry in &self.entries {
+            total += encode_one_entry(entry)?.len() as u64;
+        }
+        Ok(total)
+    }
 }
 
-impl DekuRead<(Limit<u8, for<'r> fn(&'r u8) -> bool>, (Endian, &Vec<Indic>))> for Entries {
+/// Helper for `Container`'s `#[deku(update = ...)]` expression, which can't propagate a
+/// `Result` directly; encoding failures here would also fail the subsequent real write.
+fn entries_byte_len(entries: &Entries) -> u64 {
+    entries
+        .encoded_len()
+        .expect("entries must encode to compute entries_bytes")
+}
+
+impl
+    DekuRead<(
+        Limit<u8, for<'r> fn(&'r u8) -> bool>,
+        (Endian, &Vec<Indic>, ParallelConfig, bool),
+    )> for Entries
+{
     fn read<'bs>(
         input: &'bs BitSlice<Msb0, u8>,
-        ctx: (Limit<u8, for<'r> fn(&'r u8) -> bool>, (Endian, &Vec<Indic>)),
+        ctx: (
+            Limit<u8, for<'r> fn(&'r u8) -> bool>,
+            (Endian, &Vec<Indic>, ParallelConfig, bool),
+        ),
     ) -> Result<(&'bs BitSlice<Msb0, u8>, Self), DekuError> {
-        let (bits, index) = match ctx {
-            (Limit::Bits(bits), (_, index)) => (*bits, index),
+        let (bits, index, parallel, verify_checksums) = match ctx {
+            (Limit::Bits(bits), (_, index, parallel, verify_checksums)) => {
+                (*bits, index, parallel, verify_checksums)
+            }
             _ => unreachable!("Entries should be read with bytes_read"),
         };
 
-        let mut entries = Vec::with_capacity(index.len());
-        let mut offsets = BTreeMap::new();
-
         // todo: record visited ranges and warn if there's extra
 
-        for indic in index {
-            let start = (indic.offset * 8) as usize;
-            let length = (indic.length * 8) as usize;
-            let end = start + length;
-
-            let entry = &input[start..end];
-            assert_eq!(entry.len(), length, "entry length remaining vs calculated");
-            let (post_header, header) = EntryHeader::read(entry, ())?;
-            let header_length = length - post_header.len();
-            let data_length = length - header_length;
-            let data_bits = &entry[header_length..];
-            assert_eq!(
-                data_bits.len(),
-                data_length,
-                "entry data length remaining vs calculated"
-            );
-
-            let (rest, data) = Vec::read(data_bits, ((data_length / 8).into(), ()))?;
-            assert_eq!(rest.len(), 0, "remaining data after vec read");
-
-            let ex = entries.len();
-            entries.push(Entry {
-                indic: *indic,
-                header,
-                data,
-            });
-            offsets.insert(indic.offset, ex);
+        let entries = if parallel.jobs > 1 {
+            decode_entries_parallel(input, index, &parallel)?
+        } else {
+            let mut entries = Vec::with_capacity(index.len());
+            for indic in index {
+                let start = (indic.offset * 8) as usize;
+                let end = start + (indic.length * 8) as usize;
+                entries.push(decode_one_entry(&input[start..end], *indic)?);
+            }
+            entries
+        };
+
+        let mut offsets = BTreeMap::new();
+        for (i, indic) in index.iter().enumerate() {
+            offsets.insert(indic.offset, i);
+        }
+
+        if verify_checksums {
+            let checksums_entry = entries
+                .iter()
+                .find(|entry| entry.indic.kind == IndicKind::Checksums);
+            if let Some(checksums_entry) = checksums_entry {
+                let (_, checksums) = ChecksumsEntry::from_bytes((&checksums_entry.data, 0))?;
+                verify_checksums(&entries, &checksums)?;
+            }
         }
 
         Ok((&input[bits..], Self { entries, offsets }))
     }
 }
 
-impl<T: Copy> DekuWrite<T> for Entries {
-    fn write(&self, output: &mut BitVec<Msb0, u8>, _: T) -> Result<(), DekuError> {
-        for entry in &self.entries {
-            entry.write(output, ())?;
+impl DekuWrite<(Endian, &Vec<Indic>, ParallelConfig, bool)> for Entries {
+    fn write(
+        &self,
+        output: &mut BitVec<Msb0, u8>,
+        ctx: (Endian, &Vec<Indic>, ParallelConfig, bool),
+    ) -> Result<(), DekuError> {
+        let (_, _, parallel, _) = ctx;
+        if parallel.jobs > 1 {
+            let bytes = encode_entries_parallel(&self.entries, &parallel)?;
+            let mut bits = BitVec::<Msb0, u8>::try_from_vec(bytes)
+                .map_err(|_| DekuError::Parse("entries buffer too large to bit-address".into()))?;
+            output.append(&mut bits);
+        } else {
+            for entry in &self.entries {
+                entry.write(output, ())?;
+            }
         }
 
         Ok(())
     }
 }
 
+const MAGIC: [u8; 7] = *b"\0T\0M\0v\x01";
+const CONTAINER_HEADER_LEN: usize = MAGIC.len() + size_of::<u8>() + size_of::<u64>() * 2;
+
+/// A seekable byte source a [`ContainerReader`] can fetch individual entries from without
+/// ever holding the whole archive in memory. Blanket-implemented for any `Read + Seek`, so a
+/// plain `File`, a `Cursor<Vec<u8>>`, or anything else that's seekable qualifies.
+trait BlockIO: Read + Seek {}
+impl<T: Read + Seek> BlockIO for T {}
+
+/// Reader-backed view over a tomo container: the magic, mode, and index are parsed eagerly
+/// (they're small and bounded to 16M records), but each entry's payload is only fetched and
+/// decoded on demand via [`ContainerReader::entry`], by seeking to
+/// `data_section_start + indic.offset` and reading `indic.length` bytes. This is what makes
+/// reading special entries "as soon as you've found them" practical for multi-gigabyte
+/// archives that can't be held in memory as one buffer.
+struct ContainerReader<R: BlockIO> {
+    source: R,
+    mode: Mode,
+    index: Vec<Indic>,
+    data_section_start: u64,
+    /// The authoritative length of this container's data section, straight from the header.
+    /// Used to find the start of the next catted container; deriving it from the index
+    /// instead (e.g. `max(indic.offset + indic.length)`) would get the wrong answer whenever
+    /// the data section has trailing padding or the highest-offset indic isn't physically last.
+    entries_bytes: u64,
+}
+
+impl<R: BlockIO> ContainerReader<R> {
+    /// Parses the magic, mode, and index at the source's current position, leaving the
+    /// cursor at the start of the data section.
+    fn open(mut source: R) -> Result<Self, DekuError> {
+        let mut header = [0u8; CONTAINER_HEADER_LEN];
+        source
+            .read_exact(&mut header)
+            .map_err(|e| DekuError::Parse(format!("failed to read container header: {e}")))?;
+
+        if header[0..MAGIC.len()] != MAGIC {
+            return Err(DekuError::Parse("bad container magic".into()));
+        }
+        let mode_offset = MAGIC.len();
+        let (_, mode) = Mode::from_bytes((&header[mode_offset..mode_offset + 1], 0))?;
+
+        let index_bytes_offset = mode_offset + 1;
+        let index_bytes = u64::from_le_bytes(
+            header[index_bytes_offset..index_bytes_offset + 8]
+                .try_into()
+                .unwrap(),
+        );
+
+        let entries_bytes_offset = index_bytes_offset + 8;
+        let entries_bytes = u64::from_le_bytes(
+            header[entries_bytes_offset..entries_bytes_offset + 8]
+                .try_into()
+                .unwrap(),
+        );
+
+        let index_len = (index_bytes / INDIC_SIZE) as usize;
+        let mut index_buf = vec![0u8; index_bytes as usize];
+        source
+            .read_exact(&mut index_buf)
+            .map_err(|e| DekuError::Parse(format!("failed to read container index: {e}")))?;
+        let index_bits = BitVec::<Msb0, u8>::try_from_vec(index_buf)
+            .map_err(|_| DekuError::Parse("container index not byte-aligned".into()))?;
+        let (_, index) = Vec::<Indic>::read(&index_bits, (index_len.into(), Endian::Little))?;
+
+        let data_section_start = source
+            .stream_position()
+            .map_err(|e| DekuError::Parse(format!("failed to read stream position: {e}")))?;
+
+        Ok(Self {
+            source,
+            mode,
+            index,
+            data_section_start,
+            entries_bytes,
+        })
+    }
+
+    fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    fn index(&self) -> &[Indic] {
+        &self.index
+    }
+
+    /// Fetches and decodes the entry at `position` by seeking to its recorded offset/length
+    /// rather than requiring the whole archive to be resident in memory.
+    fn entry(&mut self, position: usize) -> Result<Entry, DekuError> {
+        let indic = *self
+            .index
+            .get(position)
+            .ok_or_else(|| DekuError::Parse(format!("no indic at position {position}")))?;
+
+        self.source
+            .seek(SeekFrom::Start(self.data_section_start + indic.offset))
+            .map_err(|e| DekuError::Parse(format!("failed to seek to entry: {e}")))?;
+        let mut buf = vec![0u8; indic.length as usize];
+        self.source
+            .read_exact(&mut buf)
+            .map_err(|e| DekuError::Parse(format!("failed to read entry: {e}")))?;
+
+        let bits = BitVec::<Msb0, u8>::try_from_vec(buf)
+            .map_err(|_| DekuError::Parse("entry not byte-aligned".into()))?;
+        decode_one_entry(&bits, indic)
+    }
+
+    /// Advances past this container's data section and opens the next catted container, if
+    /// there is one, so a streaming consumer can walk stacked archives without ever holding
+    /// more than one entry's worth of data in memory.
+    fn next_container(mut self) -> Result<Option<Self>, DekuError> {
+        self.source
+            .seek(SeekFrom::Start(
+                self.data_section_start + self.entries_bytes,
+            ))
+            .map_err(|e| DekuError::Parse(format!("failed to seek past data section: {e}")))?;
+
+        let mut probe = [0u8; 1];
+        let read = self
+            .source
+            .read(&mut probe)
+            .map_err(|e| DekuError::Parse(format!("failed to probe for next container: {e}")))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        self.source
+            .seek(SeekFrom::Current(-1))
+            .map_err(|e| DekuError::Parse(format!("failed to rewind probe byte: {e}")))?;
+
+        Ok(Some(Self::open(self.source)?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const MAGIC: [u8; 7] = *b"\0T\0M\0v\x01";
-
     #[test]
     fn empty() {
         let mut data = Vec::new();
@@ -370,11 +1268,14 @@ mod tests {
         dbg!(&data);
 
         let value = Container::default();
-        let data_out = value.to_bytes().unwrap();
+        let mut output = BitVec::new();
+        value.write(&mut output, (1, true)).unwrap();
+        let data_out = output.into_vec();
         assert_eq!(data_out, data);
         dbg!(&data_out);
 
-        let ((rest, _), value) = Container::from_bytes((&data, 0)).unwrap();
+        let bits = BitVec::<Msb0, u8>::try_from_vec(data).unwrap();
+        let (rest, value) = Container::read(&bits, (1, true)).unwrap();
         assert_eq!(rest.len(), 0);
         assert_eq!(value.mode, Mode::Stacked);
         assert_eq!(value.entries.len(), 0);
@@ -392,13 +1293,14 @@ mod tests {
         let mut double = data.clone();
         double.extend(&data);
 
-        let ((rest, _), value) = Container::from_bytes((&double, 0)).unwrap();
-        assert_eq!(rest.len(), datalen);
+        let bits = BitVec::<Msb0, u8>::try_from_vec(double).unwrap();
+        let (rest, value) = Container::read(&bits, (1, true)).unwrap();
+        assert_eq!(rest.len(), datalen * 8);
         assert_eq!(value.mode, Mode::Stacked);
         assert_eq!(value.entries.len(), 0);
         assert_eq!(value.index.len(), 0);
 
-        let ((rest2, _), value) = Container::from_bytes((&rest, 0)).unwrap();
+        let (rest2, value) = Container::read(rest, (1, true)).unwrap();
         assert_eq!(rest2.len(), 0);
         assert_eq!(value.mode, Mode::Stacked);
         assert_eq!(value.entries.len(), 0);
@@ -415,27 +1317,38 @@ mod tests {
         let pathsdata = {
             let seg = b"\x01hello\0";
 
-            let mut pathdata: Vec<u8> = Vec::new();
-            pathdata.extend(&1_u32.to_le_bytes()); // count
-            pathdata.extend(seg);
+            let mut pathrecord: Vec<u8> = Vec::new();
+            pathrecord.extend(&1_u32.to_le_bytes()); // Path.segcount
+            pathrecord.extend(seg);
+
+            // record bit-offset, from the start of the PathsEntry payload (i.e. right after
+            // the count + one lookup entry): matches the `Lookup.offset` convention.
+            let record_offset_bits = ((size_of::<u32>() + LOOKUP_SIZE) * 8) as u64;
 
             let mut data = Vec::new();
             data.push(0b00_000000); // header: flags
             data.push(0x00); // header: encoding(raw)
-            data.extend(&1_u32.to_le_bytes()); // count
-            data.extend(pathdata);
+            data.extend(&1_u32.to_le_bytes()); // PathsEntry record count
+            data.extend(&1_u32.to_le_bytes()); // lookup[0].index
+            data.extend(&record_offset_bits.to_le_bytes()); // lookup[0].offset
+            data.extend(pathrecord);
             data
         };
 
         let attrsoffset = pathsoffset + pathsdata.len();
         let attrsdata = {
             let mut attr: Vec<u8> = Vec::new();
+            attr.push(0x01); // Attributes::V1
             attr.extend(&0o644_u16.to_le_bytes()); // mode
 
+            let record_offset_bits = ((size_of::<u32>() + LOOKUP_SIZE) * 8) as u64;
+
             let mut data = Vec::new();
             data.push(0b00_000000); // header: flags
             data.push(0x00); // header: encoding(raw)
-            data.extend(&1_u32.to_le_bytes()); // count
+            data.extend(&1_u32.to_le_bytes()); // AttributesEntry record count
+            data.extend(&1_u32.to_le_bytes()); // lookup[0].index
+            data.extend(&record_offset_bits.to_le_bytes()); // lookup[0].offset
             data.extend(attr);
             data
         };
@@ -501,21 +1414,390 @@ mod tests {
             data
         };
 
+        let data_len = data.len();
         ctnr.extend(&(3 * INDIC_SIZE).to_le_bytes());
-        ctnr.extend(&(data.len() as u64).to_le_bytes());
+        ctnr.extend(&(data_len as u64).to_le_bytes());
         ctnr.extend(index);
         ctnr.extend(data);
 
-        assert_eq!(ctnr.len(), 137);
+        assert_eq!(
+            ctnr.len(),
+            CONTAINER_HEADER_LEN + 3 * INDIC_SIZE as usize + data_len
+        );
         dbg!(&ctnr);
 
-        let ((rest, _), value) = Container::from_bytes((&ctnr, 0)).unwrap();
+        let bits = BitVec::<Msb0, u8>::try_from_vec(ctnr).unwrap();
+        let (rest, value) = Container::read(&bits, (1, true)).unwrap();
         dbg!(&value);
-        assert_eq!(rest, &[]);
+        assert_eq!(rest.len(), 0);
         assert_eq!(value.mode, Mode::Stacked);
         assert_eq!(value.entries.len(), 3);
         assert_eq!(value.index.len(), 3);
 
         // todo: read from high level api
     }
+
+    #[test]
+    fn checksum_seed_differs_by_region() {
+        let data = b"identical bytes, different region".to_vec();
+
+        let special_digest = checksum_digest(
+            ChecksumAlgorithm::Crc32,
+            region_csum_seed(IndicKind::Paths),
+            &data,
+        );
+        let file_digest = checksum_digest(
+            ChecksumAlgorithm::Crc32,
+            region_csum_seed(IndicKind::File),
+            &data,
+        );
+
+        assert_ne!(
+            special_digest, file_digest,
+            "a digest recorded against a special entry must not validate against file data \
+             encoding the same bytes"
+        );
+    }
+
+    #[test]
+    fn checksums_entry_with_correct_digest_verifies() {
+        let mut ctnr = Vec::new();
+        ctnr.extend(&MAGIC);
+        ctnr.push(Mode::Stacked as u8);
+
+        let filedata = {
+            let mut data = Vec::new();
+            data.push(0b00_000000); // header: flags
+            data.push(0x00); // header: encoding(raw)
+            data.extend(b"Hello world!");
+            data
+        };
+        let fileoffset = 0;
+
+        let file_digest = checksum_digest(
+            ChecksumAlgorithm::Crc32,
+            region_csum_seed(IndicKind::File),
+            b"Hello world!",
+        );
+
+        let checksumsdata = {
+            let mut record = Vec::new();
+            record.extend(&0_u32.to_le_bytes()); // target: index 0 (the file indic)
+            record.push(0x01); // ChecksumAlgorithm::Crc32
+            record.extend(&file_digest);
+
+            let mut data = Vec::new();
+            data.push(0b00_000000); // header: flags
+            data.push(0x00); // header: encoding(raw)
+            data.extend(&1_u32.to_le_bytes()); // record_count
+            data.extend(vec![0u8; LOOKUP_SIZE]); // discarded lookup table
+            data.extend(record);
+            data
+        };
+        let checksumsoffset = fileoffset + filedata.len();
+
+        let fileindic = {
+            let mut indic = Vec::new();
+            indic.push(0x01); // File
+            indic.extend(&0_u32.to_le_bytes()[0..3]); // no path
+            indic.extend(&0_u32.to_le_bytes()[0..3]); // no attr
+            indic.push(0x00); // _reserved
+            indic.extend(&(fileoffset as u64).to_le_bytes());
+            indic.extend(&(filedata.len() as u64).to_le_bytes());
+            indic
+        };
+
+        let checksumsindic = {
+            let mut indic = Vec::new();
+            indic.push(0xF1); // Checksums
+            indic.extend(&0_u32.to_le_bytes()[0..3]); // no path
+            indic.extend(&0_u32.to_le_bytes()[0..3]); // no attr
+            indic.push(0x00); // _reserved
+            indic.extend(&(checksumsoffset as u64).to_le_bytes());
+            indic.extend(&(checksumsdata.len() as u64).to_le_bytes());
+            indic
+        };
+
+        let mut index = Vec::new();
+        index.extend(fileindic);
+        index.extend(checksumsindic);
+
+        let mut data = Vec::new();
+        data.extend(&filedata);
+        data.extend(&checksumsdata);
+
+        ctnr.extend(&(2 * INDIC_SIZE).to_le_bytes());
+        ctnr.extend(&(data.len() as u64).to_le_bytes());
+        ctnr.extend(index);
+        ctnr.extend(data);
+
+        let bits = BitVec::<Msb0, u8>::try_from_vec(ctnr).unwrap();
+
+        let (_, value) =
+            Container::read(&bits, (1, true)).expect("a correct digest must verify successfully");
+        assert_eq!(value.entries.len(), 2);
+    }
+
+    #[test]
+    fn paths_entry_write_parse_get_round_trip() {
+        let paths = vec![
+            Path {
+                segcount: 1,
+                segments: vec![PathSeg::Segment(b"alpha\0".to_vec())],
+            },
+            Path {
+                segcount: 2,
+                segments: vec![PathSeg::Root, PathSeg::Segment(b"nested/beta\0".to_vec())],
+            },
+            Path {
+                segcount: 1,
+                segments: vec![PathSeg::Segment(b"gamma\0".to_vec())],
+            },
+        ];
+
+        let entry = PathsEntry::from_paths(&paths).unwrap();
+        assert_eq!(entry.len(), paths.len());
+
+        for (i, path) in paths.iter().enumerate() {
+            let got = entry.get((i + 1) as u32).unwrap();
+            assert_eq!(got.as_ref(), Some(path));
+        }
+
+        assert_eq!(entry.get(0).unwrap(), None, "index 0 is reserved/null");
+        assert_eq!(
+            entry.get((paths.len() + 1) as u32).unwrap(),
+            None,
+            "out-of-range index should be None, not an error"
+        );
+    }
+
+    #[test]
+    fn attributes_entry_write_parse_get_round_trip() {
+        let records = vec![
+            Attributes::V1(AttributesV1 { mode: 0o644 }),
+            Attributes::V2(AttributesV2 {
+                mode: 0o755,
+                uid: 1000,
+                gid: 1000,
+                mtime: 12345,
+                target: None,
+                xattrs: BTreeMap::new(),
+            }),
+        ];
+
+        let entry = AttributesEntry::from_records(&records).unwrap();
+        assert_eq!(entry.len(), records.len());
+
+        for (i, record) in records.iter().enumerate() {
+            let got = entry.get((i + 1) as u32).unwrap();
+            assert_eq!(got.as_ref(), Some(record));
+        }
+
+        assert_eq!(entry.get(0).unwrap(), None, "index 0 is reserved/null");
+        assert_eq!(
+            entry.get((records.len() + 1) as u32).unwrap(),
+            None,
+            "out-of-range index should be None, not an error"
+        );
+    }
+
+    #[test]
+    fn sparse_round_trip_preserves_trailing_hole() {
+        let mut data = vec![1, 2, 3, 4];
+        data.extend(vec![0; 32]); // trailing hole, like a disk image padded with zeros
+
+        let min_hole_size = 8;
+        let segments = sparsify(&data, min_hole_size);
+        let logical = materialize_sparse(&segments, data.len() as u64).unwrap();
+
+        assert_eq!(logical, data);
+    }
+
+    /// Builds a minimal one-file raw container's bytes, for feeding into
+    /// [`ContainerReader::open`]/catting tests.
+    fn build_raw_container(file_content: &[u8]) -> Vec<u8> {
+        let mut ctnr = Vec::new();
+        ctnr.extend(&MAGIC);
+        ctnr.push(Mode::Stacked as u8);
+
+        let filedata = {
+            let mut data = Vec::new();
+            data.push(0b00_000000); // header: flags
+            data.push(0x00); // header: encoding(raw)
+            data.extend(file_content);
+            data
+        };
+
+        let fileindic = {
+            let mut indic = Vec::new();
+            indic.push(0x01); // File
+            indic.extend(&0_u32.to_le_bytes()[0..3]); // no path
+            indic.extend(&0_u32.to_le_bytes()[0..3]); // no attr
+            indic.push(0x00); // _reserved
+            indic.extend(&0_u64.to_le_bytes()); // offset
+            indic.extend(&(filedata.len() as u64).to_le_bytes());
+            indic
+        };
+
+        ctnr.extend(&INDIC_SIZE.to_le_bytes());
+        ctnr.extend(&(filedata.len() as u64).to_le_bytes());
+        ctnr.extend(fileindic);
+        ctnr.extend(filedata);
+        ctnr
+    }
+
+    #[test]
+    fn container_reader_walks_catted_containers() {
+        use std::io::Cursor;
+
+        let first = build_raw_container(b"first container's file");
+        let second = build_raw_container(b"second container's file");
+
+        let mut catted = first.clone();
+        catted.extend(&second);
+
+        let mut reader = ContainerReader::open(Cursor::new(catted)).unwrap();
+        assert_eq!(reader.mode(), Mode::Stacked);
+        assert_eq!(reader.index().len(), 1);
+        assert_eq!(reader.entry(0).unwrap().data, b"first container's file");
+
+        let mut reader = reader
+            .next_container()
+            .unwrap()
+            .expect("a second catted container should follow");
+        assert_eq!(reader.mode(), Mode::Stacked);
+        assert_eq!(reader.index().len(), 1);
+        assert_eq!(reader.entry(0).unwrap().data, b"second container's file");
+
+        assert!(
+            reader.next_container().unwrap().is_none(),
+            "there should be no third container"
+        );
+    }
+
+    #[test]
+    fn parallel_round_trip_matches_serial() {
+        let mut ctnr = Vec::new();
+        ctnr.extend(&MAGIC);
+        ctnr.extend(vec![Mode::Stacked as u8]);
+
+        let pathsoffset = 0;
+        let pathsdata = {
+            let seg = b"\x01hello\0";
+
+            let mut pathrecord: Vec<u8> = Vec::new();
+            pathrecord.extend(&1_u32.to_le_bytes()); // Path.segcount
+            pathrecord.extend(seg);
+
+            // record bit-offset, from the start of the PathsEntry payload (i.e. right after
+            // the count + one lookup entry): matches the `Lookup.offset` convention.
+            let record_offset_bits = ((size_of::<u32>() + LOOKUP_SIZE) * 8) as u64;
+
+            let mut data = Vec::new();
+            data.push(0b00_000000); // header: flags
+            data.push(0x00); // header: encoding(raw)
+            data.extend(&1_u32.to_le_bytes()); // PathsEntry record count
+            data.extend(&1_u32.to_le_bytes()); // lookup[0].index
+            data.extend(&record_offset_bits.to_le_bytes()); // lookup[0].offset
+            data.extend(pathrecord);
+            data
+        };
+
+        let attrsoffset = pathsoffset + pathsdata.len();
+        let attrsdata = {
+            let mut attr: Vec<u8> = Vec::new();
+            attr.push(0x01); // Attributes::V1
+            attr.extend(&0o644_u16.to_le_bytes()); // mode
+
+            let record_offset_bits = ((size_of::<u32>() + LOOKUP_SIZE) * 8) as u64;
+
+            let mut data = Vec::new();
+            data.push(0b00_000000); // header: flags
+            data.push(0x00); // header: encoding(raw)
+            data.extend(&1_u32.to_le_bytes()); // AttributesEntry record count
+            data.extend(&1_u32.to_le_bytes()); // lookup[0].index
+            data.extend(&record_offset_bits.to_le_bytes()); // lookup[0].offset
+            data.extend(attr);
+            data
+        };
+
+        let fileoffset = attrsoffset + attrsdata.len();
+        let filedata = {
+            let file = b"Hello world!";
+            let fileheader = vec![0b00_000000, 0x00];
+            let mut data = Vec::new();
+            data.push(0b00_000000); // header: flags
+            data.push(0x00); // header: encoding(raw)
+            data.extend(fileheader);
+            data.extend(file);
+            data
+        };
+
+        let pathsindic = {
+            let mut indic = Vec::new();
+            indic.push(0xF0); // Paths
+            indic.extend(&0_u32.to_le_bytes()[0..3]); // no path
+            indic.extend(&0_u32.to_le_bytes()[0..3]); // no attr
+            indic.push(0x00); // _reserved
+            indic.extend(&pathsoffset.to_le_bytes()); // data offset
+            indic.extend(&pathsdata.len().to_le_bytes()); // data length
+            indic
+        };
+
+        let attrsindic = {
+            let mut indic = Vec::new();
+            indic.push(0x10); // Attributes
+            indic.extend(&0_u32.to_le_bytes()[0..3]); // no path
+            indic.extend(&0_u32.to_le_bytes()[0..3]); // no attr
+            indic.push(0x00); // _reserved
+            indic.extend(&attrsoffset.to_le_bytes()); // data offset
+            indic.extend(&attrsdata.len().to_le_bytes()); // data length
+            indic
+        };
+
+        let fileindic = {
+            let mut indic = Vec::new();
+            indic.push(0x01); // file
+            indic.extend(&1_u32.to_le_bytes()[0..3]); // path
+            indic.extend(&1_u32.to_le_bytes()[0..3]); // attr
+            indic.push(0x00); // _reserved
+            indic.extend(&fileoffset.to_le_bytes()); // data offset
+            indic.extend(&filedata.len().to_le_bytes()); // data length
+            indic
+        };
+
+        let index = {
+            let mut index = Vec::new();
+            index.extend(pathsindic);
+            index.extend(attrsindic);
+            index.extend(fileindic);
+            index
+        };
+
+        let data = {
+            let mut data = Vec::new();
+            data.extend(pathsdata);
+            data.extend(attrsdata);
+            data.extend(filedata);
+            data
+        };
+
+        ctnr.extend(&(3 * INDIC_SIZE).to_le_bytes());
+        ctnr.extend(&(data.len() as u64).to_le_bytes());
+        ctnr.extend(index);
+        ctnr.extend(data);
+
+        let bits = BitVec::<Msb0, u8>::try_from_vec(ctnr).unwrap();
+
+        let (_, serial) = Container::read(&bits, (1, true)).unwrap();
+        let mut serial_bytes = BitVec::<Msb0, u8>::new();
+        serial.write(&mut serial_bytes, (1, true)).unwrap();
+
+        let (_, parallel) = Container::read(&bits, (4, true)).unwrap();
+        let mut parallel_bytes = BitVec::<Msb0, u8>::new();
+        parallel.write(&mut parallel_bytes, (4, true)).unwrap();
+
+        assert_eq!(parallel.entries.len(), serial.entries.len());
+        assert_eq!(parallel_bytes, serial_bytes);
+    }
 }